@@ -0,0 +1,10 @@
+use crate::variables::Variable;
+use serde::Deserialize;
+
+/// Template-level configuration read from `cargo-generate.toml`.
+#[derive(Debug, Deserialize, Default)]
+pub struct TemplateConfig {
+    /// `[[placeholders]]` entries prompted for (or resolved via `--define`)
+    /// before the template is rendered.
+    pub placeholders: Option<Vec<Variable>>,
+}
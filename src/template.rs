@@ -3,14 +3,18 @@ use crate::config::TemplateConfig;
 use crate::emoji;
 use crate::include_exclude::*;
 use crate::projectname::ProjectName;
+use crate::variables;
 use console::style;
 use failure;
-use heck::{CamelCase, KebabCase, SnakeCase};
+use heck::{
+    CamelCase, KebabCase, MixedCase, ShoutyKebabCase, ShoutySnakeCase, SnakeCase, TitleCase,
+};
 use indicatif::ProgressBar;
 use liquid;
 use quicli::prelude::*;
+use std::collections::HashMap;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 use walkdir::{DirEntry, WalkDir};
 
 fn engine() -> liquid::Parser {
@@ -20,6 +24,13 @@ fn engine() -> liquid::Parser {
         .filter(KebabCaseFilterParser)
         .filter(PascalCaseFilterParser)
         .filter(SnakeCaseFilterParser)
+        .filter(ShoutySnakeCaseFilterParser)
+        .filter(ShoutyKebabCaseFilterParser)
+        .filter(TitleCaseFilterParser)
+        .filter(LowerCamelCaseFilterParser)
+        .filter(DottedPascalCaseFilterParser)
+        .filter(EnvVarFilterParser)
+        .filter(EnvVarOrDefaultFilterParser)
         .build()
         .expect("can't fail due to no partials support")
 }
@@ -50,7 +61,7 @@ impl liquid::compiler::Filter for KebabCaseFilter {
 
 #[derive(Clone, liquid_derive::ParseFilter, liquid_derive::FilterReflection)]
 #[filter(
-    name = "kebab_case",
+    name = "pascal_case",
     description = "Change text to PascalCase.",
     parsed(PascalCaseFilter)
 )]
@@ -74,14 +85,14 @@ impl liquid::compiler::Filter for PascalCaseFilter {
 
 #[derive(Clone, liquid_derive::ParseFilter, liquid_derive::FilterReflection)]
 #[filter(
-    name = "kebab_case",
+    name = "snake_case",
     description = "Change text to snake_case.",
     parsed(SnakeCaseFilter)
 )]
 pub struct SnakeCaseFilterParser;
 
 #[derive(Debug, Default, liquid_derive::Display_filter)]
-#[name = "pascal_case"]
+#[name = "snake_case"]
 struct SnakeCaseFilter;
 
 impl liquid::compiler::Filter for SnakeCaseFilter {
@@ -96,8 +107,200 @@ impl liquid::compiler::Filter for SnakeCaseFilter {
     }
 }
 
+#[derive(Clone, liquid_derive::ParseFilter, liquid_derive::FilterReflection)]
+#[filter(
+    name = "shouty_snake_case",
+    description = "Change text to SHOUTY_SNAKE_CASE.",
+    parsed(ShoutySnakeCaseFilter)
+)]
+pub struct ShoutySnakeCaseFilterParser;
+
+#[derive(Debug, Default, liquid_derive::Display_filter)]
+#[name = "shouty_snake_case"]
+struct ShoutySnakeCaseFilter;
+
+impl liquid::compiler::Filter for ShoutySnakeCaseFilter {
+    fn evaluate(
+        &self,
+        input: &liquid::value::Value,
+        _context: &liquid::interpreter::Context,
+    ) -> Result<liquid::value::Value, liquid::error::Error> {
+        let input = input.to_str();
+        let input = input.as_ref().to_shouty_snake_case();
+        Ok(liquid::value::Value::scalar(input))
+    }
+}
+
+#[derive(Clone, liquid_derive::ParseFilter, liquid_derive::FilterReflection)]
+#[filter(
+    name = "shouty_kebab_case",
+    description = "Change text to SHOUTY-KEBAB-CASE.",
+    parsed(ShoutyKebabCaseFilter)
+)]
+pub struct ShoutyKebabCaseFilterParser;
+
+#[derive(Debug, Default, liquid_derive::Display_filter)]
+#[name = "shouty_kebab_case"]
+struct ShoutyKebabCaseFilter;
+
+impl liquid::compiler::Filter for ShoutyKebabCaseFilter {
+    fn evaluate(
+        &self,
+        input: &liquid::value::Value,
+        _context: &liquid::interpreter::Context,
+    ) -> Result<liquid::value::Value, liquid::error::Error> {
+        let input = input.to_str();
+        let input = input.as_ref().to_shouty_kebab_case();
+        Ok(liquid::value::Value::scalar(input))
+    }
+}
+
+#[derive(Clone, liquid_derive::ParseFilter, liquid_derive::FilterReflection)]
+#[filter(
+    name = "title_case",
+    description = "Change text to Title Case.",
+    parsed(TitleCaseFilter)
+)]
+pub struct TitleCaseFilterParser;
+
+#[derive(Debug, Default, liquid_derive::Display_filter)]
+#[name = "title_case"]
+struct TitleCaseFilter;
+
+impl liquid::compiler::Filter for TitleCaseFilter {
+    fn evaluate(
+        &self,
+        input: &liquid::value::Value,
+        _context: &liquid::interpreter::Context,
+    ) -> Result<liquid::value::Value, liquid::error::Error> {
+        let input = input.to_str();
+        let input = input.as_ref().to_title_case();
+        Ok(liquid::value::Value::scalar(input))
+    }
+}
+
+#[derive(Clone, liquid_derive::ParseFilter, liquid_derive::FilterReflection)]
+#[filter(
+    name = "lower_camel_case",
+    description = "Change text to lowerCamelCase.",
+    parsed(LowerCamelCaseFilter)
+)]
+pub struct LowerCamelCaseFilterParser;
+
+#[derive(Debug, Default, liquid_derive::Display_filter)]
+#[name = "lower_camel_case"]
+struct LowerCamelCaseFilter;
+
+impl liquid::compiler::Filter for LowerCamelCaseFilter {
+    fn evaluate(
+        &self,
+        input: &liquid::value::Value,
+        _context: &liquid::interpreter::Context,
+    ) -> Result<liquid::value::Value, liquid::error::Error> {
+        let input = input.to_str();
+        let input = input.as_ref().to_mixed_case();
+        Ok(liquid::value::Value::scalar(input))
+    }
+}
+
+#[derive(Clone, liquid_derive::ParseFilter, liquid_derive::FilterReflection)]
+#[filter(
+    name = "dotted_pascal_case",
+    description = "Change each dot-separated segment of text to PascalCase, keeping the dots.",
+    parsed(DottedPascalCaseFilter)
+)]
+pub struct DottedPascalCaseFilterParser;
+
+#[derive(Debug, Default, liquid_derive::Display_filter)]
+#[name = "dotted_pascal_case"]
+struct DottedPascalCaseFilter;
+
+impl liquid::compiler::Filter for DottedPascalCaseFilter {
+    fn evaluate(
+        &self,
+        input: &liquid::value::Value,
+        _context: &liquid::interpreter::Context,
+    ) -> Result<liquid::value::Value, liquid::error::Error> {
+        let input = input.to_str();
+        let input = input
+            .as_ref()
+            .split('.')
+            .map(|segment| segment.to_camel_case())
+            .collect::<Vec<_>>()
+            .join(".");
+        Ok(liquid::value::Value::scalar(input))
+    }
+}
+
+#[derive(Clone, liquid_derive::ParseFilter, liquid_derive::FilterReflection)]
+#[filter(
+    name = "env_var",
+    description = "Resolve an environment variable, erroring out if it is unset.",
+    parsed(EnvVarFilter)
+)]
+pub struct EnvVarFilterParser;
+
+#[derive(Debug, Default, liquid_derive::Display_filter)]
+#[name = "env_var"]
+struct EnvVarFilter;
+
+impl liquid::compiler::Filter for EnvVarFilter {
+    fn evaluate(
+        &self,
+        input: &liquid::value::Value,
+        _context: &liquid::interpreter::Context,
+    ) -> Result<liquid::value::Value, liquid::error::Error> {
+        let name = input.to_str();
+        let value = std::env::var(name.as_ref()).map_err(|_e| {
+            liquid::error::Error::with_msg(format!("environment variable `{}` is not set", name))
+        })?;
+        Ok(liquid::value::Value::scalar(value))
+    }
+}
+
+#[derive(Debug, liquid_derive::FilterParameters)]
+struct EnvVarOrDefaultArgs {
+    #[parameter(
+        description = "The value to use if the variable is unset.",
+        arg_type = "str"
+    )]
+    default: liquid::compiler::Expression,
+}
+
+#[derive(Clone, liquid_derive::ParseFilter, liquid_derive::FilterReflection)]
+#[filter(
+    name = "env_var_or_default",
+    description = "Resolve an environment variable, falling back to a default if it is unset.",
+    parameters(EnvVarOrDefaultArgs),
+    parsed(EnvVarOrDefaultFilter)
+)]
+pub struct EnvVarOrDefaultFilterParser;
+
+#[derive(Debug, liquid_derive::FromFilterParameters, liquid_derive::Display_filter)]
+#[name = "env_var_or_default"]
+struct EnvVarOrDefaultFilter {
+    #[parameters]
+    args: EnvVarOrDefaultArgs,
+}
+
+impl liquid::compiler::Filter for EnvVarOrDefaultFilter {
+    fn evaluate(
+        &self,
+        input: &liquid::value::Value,
+        context: &liquid::interpreter::Context,
+    ) -> Result<liquid::value::Value, liquid::error::Error> {
+        let args = self.args.evaluate(context)?;
+        let name = input.to_str();
+        let value =
+            std::env::var(name.as_ref()).unwrap_or_else(|_e| args.default.to_str().into_owned());
+        Ok(liquid::value::Value::scalar(value))
+    }
+}
+
 pub fn substitute(
     name: &ProjectName,
+    template_config: Option<&TemplateConfig>,
+    defines: &HashMap<String, String>,
     force: bool,
 ) -> Result<liquid::value::Object, failure::Error> {
     let project_name = if force { name.raw() } else { name.kebab_case() };
@@ -115,6 +318,10 @@ pub fn substitute(
     template.insert("authors".into(), liquid::value::Value::scalar(authors));
     template.insert("username".into(), liquid::value::Value::scalar(username));
 
+    if let Some(placeholders) = template_config.and_then(|config| config.placeholders.as_ref()) {
+        variables::resolve_variables(placeholders, defines, &mut template)?;
+    }
+
     Ok(template)
 }
 
@@ -124,10 +331,6 @@ pub fn walk_dir(
     template_config: Option<TemplateConfig>,
     pbar: ProgressBar,
 ) -> Result<(), failure::Error> {
-    fn is_dir(entry: &DirEntry) -> bool {
-        entry.file_type().is_dir()
-    }
-
     fn is_git_metadata(entry: &DirEntry) -> bool {
         entry
             .path()
@@ -143,16 +346,51 @@ pub fn walk_dir(
         |config| Matcher::new(config, project_dir),
     )?;
 
-    for entry in WalkDir::new(project_dir) {
+    let mut entries = WalkDir::new(project_dir).into_iter();
+    while let Some(entry) = entries.next() {
         let entry = entry?;
-        if is_dir(&entry) || is_git_metadata(&entry) {
+        if is_git_metadata(&entry) {
             continue;
         }
 
         let filename = entry.path();
+
+        // The filename itself may be a template, e.g. `{% if with_ci %}ci.yml{% endif %}`;
+        // only the final path component is rendered, since `filename` carries the
+        // full `project_dir`-prefixed path and would never render empty otherwise.
+        let file_name_str = entry.file_name().to_str().expect("filename as string");
+        let parsed_file_name = engine.clone().parse(file_name_str)?.render(&template)?;
+
+        if entry.file_type().is_dir() {
+            if parsed_file_name.is_empty() {
+                fs::remove_dir_all(filename).with_context(|_e| {
+                    format!(
+                        "{} {} `{}`",
+                        emoji::ERROR,
+                        style("Error removing").bold().red(),
+                        style(filename.display()).bold()
+                    )
+                })?;
+                entries.skip_current_dir();
+            }
+            continue;
+        }
+
         let relative_path = filename.strip_prefix(project_dir)?;
         pbar.set_message(&filename.display().to_string());
 
+        if parsed_file_name.is_empty() {
+            fs::remove_file(filename).with_context(|_e| {
+                format!(
+                    "{} {} `{}`",
+                    emoji::ERROR,
+                    style("Error removing").bold().red(),
+                    style(filename.display()).bold()
+                )
+            })?;
+            continue;
+        }
+
         if matcher.should_include(relative_path) {
             let new_contents = engine
                 .clone()
@@ -176,16 +414,13 @@ pub fn walk_dir(
             })?;
         }
 
-        // Check if the filename does not contains any
-        // template
-        let filename_str = filename.to_str().expect("filename as string");
-        let parsed_filename = engine.clone().parse(filename_str)?.render(&template)?;
-        fs::rename(&filename, Path::new(&parsed_filename)).with_context(|_e| {
+        let new_filename = filename.with_file_name(&parsed_file_name);
+        fs::rename(&filename, &new_filename).with_context(|_e| {
             format!(
                 "{} {} '{}'",
                 emoji::ERROR,
                 style("Error renaming").bold().red(),
-                style(parsed_filename).bold()
+                style(new_filename.display()).bold()
             )
         })?;
     }
@@ -0,0 +1,121 @@
+use crate::emoji;
+use console::style;
+use failure;
+use quicli::prelude::*;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// A single entry in a template's `[[placeholders]]` table: a Liquid
+/// variable that is gathered from the user (or a `--define` override)
+/// before `walk_dir()` renders the template.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Variable {
+    pub name: String,
+    pub prompt: String,
+    pub default: Option<String>,
+    pub choices: Option<Vec<String>>,
+    pub validation: Option<String>,
+    pub only_if: Option<Condition>,
+}
+
+/// Gates a `Variable` on a previously answered one, e.g.
+/// `only_if = { name = "backend", value = "postgres" }`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Condition {
+    pub name: String,
+    pub value: String,
+}
+
+impl Variable {
+    fn is_active(&self, answers: &HashMap<String, String>) -> bool {
+        match &self.only_if {
+            Some(condition) => answers
+                .get(&condition.name)
+                .map_or(false, |value| value == &condition.value),
+            None => true,
+        }
+    }
+
+    fn prompt_for_value(&self) -> Result<String, failure::Error> {
+        if let Some(choices) = &self.choices {
+            let default = self
+                .default
+                .as_ref()
+                .and_then(|default| choices.iter().position(|choice| choice == default))
+                .unwrap_or(0);
+            let selection = dialoguer::Select::new()
+                .with_prompt(&self.prompt)
+                .items(choices)
+                .default(default)
+                .interact()?;
+            Ok(choices[selection].clone())
+        } else {
+            let mut input = dialoguer::Input::<String>::new();
+            input.with_prompt(&self.prompt);
+            if let Some(default) = &self.default {
+                input.default(default.clone());
+            }
+            Ok(input.interact()?)
+        }
+    }
+
+    fn validate(&self, value: &str) -> Result<(), failure::Error> {
+        if let Some(pattern) = &self.validation {
+            let regex = Regex::new(pattern)?;
+            ensure!(
+                regex.is_match(value),
+                "{} {} `{}` does not match `{}`",
+                emoji::ERROR,
+                style("Invalid value").bold().red(),
+                value,
+                pattern
+            );
+        }
+        Ok(())
+    }
+
+    /// Resolve the final value for this variable: a `--define` override is
+    /// used (and validated) as-is, otherwise the user is re-prompted until
+    /// the answer passes `validation`.
+    fn resolve(&self, defines: &HashMap<String, String>) -> Result<String, failure::Error> {
+        if let Some(value) = defines.get(&self.name) {
+            self.validate(value)?;
+            return Ok(value.clone());
+        }
+
+        loop {
+            let value = self.prompt_for_value()?;
+            match self.validate(&value) {
+                Ok(()) => return Ok(value),
+                Err(e) => println!("{} {}", emoji::ERROR, e),
+            }
+        }
+    }
+}
+
+/// Resolve every active placeholder in declaration order and merge the
+/// answers into `template`, so that earlier answers are visible to later
+/// `only_if` conditions.
+pub fn resolve_variables(
+    variables: &[Variable],
+    defines: &HashMap<String, String>,
+    template: &mut liquid::value::Object,
+) -> Result<(), failure::Error> {
+    let mut answers: HashMap<String, String> = HashMap::new();
+
+    for variable in variables {
+        if !variable.is_active(&answers) {
+            continue;
+        }
+
+        let value = variable.resolve(defines)?;
+        answers.insert(variable.name.clone(), value.clone());
+        template.insert(
+            variable.name.clone().into(),
+            liquid::value::Value::scalar(value),
+        );
+    }
+
+    Ok(())
+}